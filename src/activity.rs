@@ -0,0 +1,229 @@
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::actor::CompoundActorReference;
+use crate::context::Context;
+use crate::entity::{Entity, EntityType};
+use crate::object::{Object, ObjectReference, ObjectTrait};
+
+/// Discriminates the kind of activity without having to compare
+/// [Entity::object_type] against a string everywhere.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityType {
+    Create,
+    Update,
+    Delete,
+    Follow,
+    Accept,
+    Reject,
+    Add,
+    Remove,
+    Like,
+    Announce,
+    Undo,
+
+    /// Any activity type this crate does not model explicitly yet.
+    #[serde(other)]
+    Other,
+}
+
+impl From<EntityType> for ActivityType {
+    fn from(entity_type: EntityType) -> Self {
+        match entity_type {
+            EntityType::Create => ActivityType::Create,
+            EntityType::Update => ActivityType::Update,
+            EntityType::Delete => ActivityType::Delete,
+            EntityType::Follow => ActivityType::Follow,
+            EntityType::Accept => ActivityType::Accept,
+            EntityType::Reject => ActivityType::Reject,
+            EntityType::Add => ActivityType::Add,
+            EntityType::Remove => ActivityType::Remove,
+            EntityType::Like => ActivityType::Like,
+            EntityType::Announce => ActivityType::Announce,
+            EntityType::Undo => ActivityType::Undo,
+            _ => ActivityType::Other,
+        }
+    }
+}
+
+/// An ActivityPub activity, e.g. `Create`, `Follow`, `Announce` or
+/// `Undo`. This is what actually flows through inboxes and outboxes;
+/// [Object] only models what activities act upon.
+///
+/// `O` controls how `object` is deserialized: use `Activity<Object>`
+/// when the activity is expected to embed its object, or the default
+/// `Activity<ObjectReference>` when the object may also be a bare IRI.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Activity<O = ObjectReference> {
+    /// Embedded [Entity] properties.
+    #[serde(flatten)]
+    pub entity: Entity,
+
+    /// Unique activity identifier.
+    pub id: url::Url,
+
+    /// Actor performing the activity.
+    pub actor: CompoundActorReference,
+
+    /// Object the activity acts upon.
+    pub object: O,
+
+    /// Secondary object of the activity, e.g. the collection a
+    /// `Follow`'s object is being added to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<ObjectReference>,
+
+    /// To whom the activity is sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<CompoundActorReference>,
+
+    /// Recipients to receive a copy of the activity.
+    #[cfg(feature = "more_properties")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cc: Option<CompoundActorReference>,
+
+    /// When the activity was published.
+    #[cfg(feature = "chrono")]
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "crate::temporal")]
+    pub published: Option<DateTime<Utc>>,
+}
+
+impl<O> Activity<O> {
+    /// Returns the id of the actor performing this activity.
+    pub fn actor_id(&self) -> Option<&url::Url> {
+        self.actor.any_url()
+    }
+
+    /// Returns the [ActivityType] this activity's `type` maps to, so
+    /// callers can `match` on Create/Follow/Undo/... instead of
+    /// comparing [EntityType] against a string.
+    pub fn activity_type(&self) -> ActivityType {
+        self.entity.object_type.into()
+    }
+}
+
+impl Activity<ObjectReference> {
+    /// Returns the id of the object this activity acts upon, regardless
+    /// of whether it was embedded or referenced by URL.
+    ///
+    /// Named distinctly from [ObjectTrait::object_id], which returns
+    /// this activity's own id rather than its object's — an inherent
+    /// method of the same name would silently shadow the trait method
+    /// at call sites on a concrete `Activity`, so the same call would
+    /// return a different id there than through `impl ObjectTrait`.
+    pub fn target_object_id(&self) -> &url::Url {
+        self.object.object_id()
+    }
+}
+
+impl Activity<Object> {
+    /// Returns the id of the embedded object this activity acts upon.
+    ///
+    /// Named distinctly from [ObjectTrait::object_id], for the same
+    /// reason as `Activity<ObjectReference>::target_object_id`.
+    pub fn target_object_id(&self) -> &url::Url {
+        self.object.object_id()
+    }
+}
+
+impl<O> ObjectTrait for Activity<O> {
+    fn context(&self) -> Option<&Context> {
+        self.entity.context.as_ref()
+    }
+
+    fn object_id(&self) -> &url::Url {
+        &self.id
+    }
+
+    fn entity_type(&self) -> EntityType {
+        self.entity.object_type
+    }
+
+    #[cfg(feature = "chrono")]
+    fn published(&self) -> Option<DateTime<Utc>> {
+        self.published
+    }
+
+    // Activities have no `updated`/`startTime`/`endTime` properties of
+    // their own; these describe the window the activity's `object`
+    // spans, not the activity itself.
+    #[cfg(feature = "chrono")]
+    fn updated(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    #[cfg(feature = "chrono")]
+    fn start_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    #[cfg(feature = "chrono")]
+    fn end_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity_json(object: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "type": "Follow",
+            "id": "https://example.com/activities/1",
+            "actor": "https://example.com/users/alice",
+            "object": object,
+        })
+    }
+
+    #[test]
+    fn activity_type_from_entity_type_maps_known_variants() {
+        assert_eq!(ActivityType::from(EntityType::Follow), ActivityType::Follow);
+        assert_eq!(ActivityType::from(EntityType::Announce), ActivityType::Announce);
+    }
+
+    #[test]
+    fn activity_type_from_entity_type_maps_unknown_to_other() {
+        assert_eq!(ActivityType::from(EntityType::Note), ActivityType::Other);
+    }
+
+    #[test]
+    fn deserializes_with_bare_object_reference() {
+        let json = activity_json(serde_json::json!("https://example.com/users/bob"));
+        let activity: Activity<ObjectReference> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(activity.activity_type(), ActivityType::Follow);
+        assert_eq!(
+            activity.actor_id(),
+            Some(&"https://example.com/users/alice".parse::<url::Url>().unwrap()),
+        );
+        assert_eq!(
+            activity.target_object_id(),
+            &"https://example.com/users/bob".parse::<url::Url>().unwrap(),
+        );
+    }
+
+    #[test]
+    fn deserializes_with_embedded_object() {
+        let json = activity_json(serde_json::json!({
+            "type": "Person",
+            "id": "https://example.com/users/bob",
+        }));
+        let activity: Activity<Object> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            activity.target_object_id(),
+            &"https://example.com/users/bob".parse::<url::Url>().unwrap(),
+        );
+    }
+
+    #[test]
+    fn object_trait_object_id_is_the_activitys_own_id_not_its_targets() {
+        let json = activity_json(serde_json::json!("https://example.com/users/bob"));
+        let activity: Activity<ObjectReference> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(ObjectTrait::object_id(&activity), &activity.id);
+        assert_ne!(ObjectTrait::object_id(&activity), activity.target_object_id());
+    }
+}
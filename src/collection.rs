@@ -0,0 +1,398 @@
+use async_stream::try_stream;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::entity::Entity;
+use crate::object::{ObjectReference, UrlReference};
+use crate::resolver::{ResolveError, Resolver};
+
+/// Upper bound on how many pages [Collection::pages]/[OrderedCollection::pages]
+/// will follow via `next`, independent of [Resolver]'s own depth limit.
+/// Bounds a misbehaving or malicious server that loops `next` back to
+/// an earlier page.
+const MAX_PAGES: u32 = 10_000;
+
+/// Either the page embedded directly, or a reference to fetch it
+/// through. Real servers use both shapes for a collection's `first`:
+/// some inline the whole first page, others only ever point at it by
+/// URL.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum PageReference<P> {
+    /// Page embedded directly, not one more hop away.
+    Page(Box<P>),
+
+    /// Indirection to the page, to be fetched through a [Resolver].
+    Reference(UrlReference),
+}
+
+impl<P> PageReference<P>
+where
+    P: Clone + serde::de::DeserializeOwned,
+{
+    /// Returns the page, fetching it through `resolver` if it wasn't
+    /// already embedded.
+    async fn fetch(&self, resolver: &Resolver) -> Result<P, ResolveError> {
+        match self {
+            PageReference::Page(page) => Ok((**page).clone()),
+            PageReference::Reference(reference) => fetch_page(reference, resolver).await,
+        }
+    }
+}
+
+/// ActivityPub collection, e.g. an actor's outbox, followers or
+/// replies. Most collections are paged; `items`/`ordered_items` is
+/// only populated when the server inlines everything up front.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Collection {
+    /// Embedded [Entity] properties.
+    #[serde(flatten)]
+    pub entity: Entity,
+
+    /// Unique collection identifier.
+    pub id: url::Url,
+
+    /// Total number of items in the collection, across all pages.
+    #[serde(rename = "totalItems", skip_serializing_if = "Option::is_none")]
+    pub total_items: Option<u64>,
+
+    /// First page of the collection, either embedded or referenced by
+    /// URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first: Option<PageReference<CollectionPage>>,
+
+    /// Last page of the collection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last: Option<UrlReference>,
+
+    /// Page currently being viewed, for collections that track one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<UrlReference>,
+
+    /// Items inlined directly on the collection, with no paging.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec<ObjectReference>>,
+}
+
+/// Same as [Collection], but items have a defined order.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OrderedCollection {
+    /// Embedded [Entity] properties.
+    #[serde(flatten)]
+    pub entity: Entity,
+
+    /// Unique collection identifier.
+    pub id: url::Url,
+
+    /// Total number of items in the collection, across all pages.
+    #[serde(rename = "totalItems", skip_serializing_if = "Option::is_none")]
+    pub total_items: Option<u64>,
+
+    /// First page of the collection, either embedded or referenced by
+    /// URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first: Option<PageReference<OrderedCollectionPage>>,
+
+    /// Last page of the collection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last: Option<UrlReference>,
+
+    /// Page currently being viewed, for collections that track one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<UrlReference>,
+
+    /// Items inlined directly on the collection, with no paging.
+    #[serde(rename = "orderedItems", skip_serializing_if = "Option::is_none")]
+    pub ordered_items: Option<Vec<ObjectReference>>,
+}
+
+/// A single page of a [Collection].
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CollectionPage {
+    /// Embedded [Entity] properties.
+    #[serde(flatten)]
+    pub entity: Entity,
+
+    /// Unique page identifier.
+    pub id: url::Url,
+
+    /// Total number of items in the collection, across all pages.
+    #[serde(rename = "totalItems", skip_serializing_if = "Option::is_none")]
+    pub total_items: Option<u64>,
+
+    /// Next page, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<UrlReference>,
+
+    /// Previous page, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev: Option<UrlReference>,
+
+    /// Collection this page belongs to.
+    #[serde(rename = "partOf", skip_serializing_if = "Option::is_none")]
+    pub part_of: Option<UrlReference>,
+
+    /// Items carried by this page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec<ObjectReference>>,
+}
+
+/// A single page of an [OrderedCollection].
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OrderedCollectionPage {
+    /// Embedded [Entity] properties.
+    #[serde(flatten)]
+    pub entity: Entity,
+
+    /// Unique page identifier.
+    pub id: url::Url,
+
+    /// Total number of items in the collection, across all pages.
+    #[serde(rename = "totalItems", skip_serializing_if = "Option::is_none")]
+    pub total_items: Option<u64>,
+
+    /// Next page, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<UrlReference>,
+
+    /// Previous page, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev: Option<UrlReference>,
+
+    /// Collection this page belongs to.
+    #[serde(rename = "partOf", skip_serializing_if = "Option::is_none")]
+    pub part_of: Option<UrlReference>,
+
+    /// Items carried by this page, in order.
+    #[serde(rename = "orderedItems", skip_serializing_if = "Option::is_none")]
+    pub ordered_items: Option<Vec<ObjectReference>>,
+}
+
+/// Fetches the page referenced by `reference`, which is usually a bare
+/// URL but may also be a `Link`; [UrlReference::any_url] handles both
+/// shapes uniformly.
+async fn fetch_page<T>(reference: &UrlReference, resolver: &Resolver) -> Result<T, ResolveError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let url = reference.any_url().ok_or(ResolveError::NoUrl)?;
+
+    resolver.fetch(url).await
+}
+
+impl Collection {
+    /// Streams every page of this collection, starting from `first`
+    /// and following `next` until it is absent. Each page is yielded
+    /// as soon as it is fetched, before the next one is requested, so
+    /// a failure partway through does not discard pages already
+    /// fetched successfully. Bounded by [MAX_PAGES], independent of
+    /// [Resolver]'s own indirection depth limit.
+    pub fn pages<'a>(
+        &'a self,
+        resolver: &'a Resolver,
+    ) -> impl Stream<Item = Result<CollectionPage, ResolveError>> + 'a {
+        try_stream! {
+            let Some(first) = &self.first else { return };
+            let mut page = first.fetch(resolver).await?;
+            let mut remaining = MAX_PAGES;
+
+            loop {
+                if remaining == 0 {
+                    Err(ResolveError::TooManyPages(MAX_PAGES))?;
+                }
+
+                remaining -= 1;
+
+                let next = page.next.clone();
+                yield page;
+
+                let Some(next) = next else { break };
+                page = fetch_page(&next, resolver).await?;
+            }
+        }
+    }
+
+    /// Flattens [Collection::pages] into a stream of individual
+    /// [ObjectReference]s, also covering the case where `items` was
+    /// inlined on the collection itself with no paging at all.
+    pub fn items<'a>(
+        &'a self,
+        resolver: &'a Resolver,
+    ) -> impl Stream<Item = Result<ObjectReference, ResolveError>> + 'a {
+        try_stream! {
+            if let Some(items) = &self.items {
+                for item in items {
+                    yield item.clone();
+                }
+
+                return;
+            }
+
+            for await page in self.pages(resolver) {
+                let page = page?;
+
+                for item in page.items.into_iter().flatten() {
+                    yield item;
+                }
+            }
+        }
+    }
+}
+
+impl OrderedCollection {
+    /// Streams every page of this collection, starting from `first`
+    /// and following `next` until it is absent. Each page is yielded
+    /// as soon as it is fetched, before the next one is requested, so
+    /// a failure partway through does not discard pages already
+    /// fetched successfully. Bounded by [MAX_PAGES], independent of
+    /// [Resolver]'s own indirection depth limit.
+    pub fn pages<'a>(
+        &'a self,
+        resolver: &'a Resolver,
+    ) -> impl Stream<Item = Result<OrderedCollectionPage, ResolveError>> + 'a {
+        try_stream! {
+            let Some(first) = &self.first else { return };
+            let mut page = first.fetch(resolver).await?;
+            let mut remaining = MAX_PAGES;
+
+            loop {
+                if remaining == 0 {
+                    Err(ResolveError::TooManyPages(MAX_PAGES))?;
+                }
+
+                remaining -= 1;
+
+                let next = page.next.clone();
+                yield page;
+
+                let Some(next) = next else { break };
+                page = fetch_page(&next, resolver).await?;
+            }
+        }
+    }
+
+    /// Flattens [OrderedCollection::pages] into a stream of individual
+    /// [ObjectReference]s, also covering the case where `ordered_items`
+    /// was inlined on the collection itself with no paging at all.
+    pub fn items<'a>(
+        &'a self,
+        resolver: &'a Resolver,
+    ) -> impl Stream<Item = Result<ObjectReference, ResolveError>> + 'a {
+        try_stream! {
+            if let Some(items) = &self.ordered_items {
+                for item in items {
+                    yield item.clone();
+                }
+
+                return;
+            }
+
+            for await page in self.pages(resolver) {
+                let page = page?;
+
+                for item in page.ordered_items.into_iter().flatten() {
+                    yield item;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(id: &str, next: Option<&str>) -> CollectionPage {
+        CollectionPage {
+            entity: Entity::new(crate::entity::EntityType::CollectionPage),
+            id: id.parse().unwrap(),
+            total_items: None,
+            next: next.map(|url| UrlReference::Url(url.parse().unwrap())),
+            prev: None,
+            part_of: None,
+            items: Some(vec![ObjectReference::Url(id.parse().unwrap())]),
+        }
+    }
+
+    #[test]
+    fn page_reference_page_variant_holds_embedded_page() {
+        let embedded = page("https://example.com/outbox?page=1", None);
+        let reference = PageReference::Page(Box::new(embedded.clone()));
+
+        match reference {
+            PageReference::Page(boxed) => assert_eq!(boxed.id, embedded.id),
+            PageReference::Reference(_) => panic!("expected an embedded page"),
+        }
+    }
+
+    #[test]
+    fn page_reference_deserializes_embedded_page_object() {
+        let json = serde_json::json!({
+            "type": "CollectionPage",
+            "id": "https://example.com/outbox?page=1",
+            "items": [],
+        });
+
+        let reference: PageReference<CollectionPage> =
+            serde_json::from_value(json).expect("embedded page object should deserialize");
+
+        assert!(matches!(reference, PageReference::Page(_)));
+    }
+
+    #[test]
+    fn page_reference_deserializes_bare_url() {
+        let json = serde_json::json!("https://example.com/outbox?page=1");
+
+        let reference: PageReference<CollectionPage> =
+            serde_json::from_value(json).expect("bare URL should deserialize");
+
+        assert!(matches!(reference, PageReference::Reference(_)));
+    }
+
+    #[tokio::test]
+    async fn pages_stream_is_empty_without_first_page() {
+        use futures::StreamExt;
+
+        let collection = Collection {
+            entity: Entity::new(crate::entity::EntityType::Collection),
+            id: "https://example.com/outbox".parse().unwrap(),
+            total_items: None,
+            first: None,
+            last: None,
+            current: None,
+            items: None,
+        };
+
+        let resolver = Resolver::new(reqwest::Client::builder()).unwrap();
+        let pages: Vec<_> = collection.pages(&resolver).collect().await;
+
+        assert!(pages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn pages_stream_yields_single_page_with_no_next() {
+        use futures::StreamExt;
+
+        let only_page = page("https://example.com/outbox?page=1", None);
+
+        let collection = Collection {
+            entity: Entity::new(crate::entity::EntityType::Collection),
+            id: "https://example.com/outbox".parse().unwrap(),
+            total_items: None,
+            first: Some(PageReference::Page(Box::new(only_page.clone()))),
+            last: None,
+            current: None,
+            items: None,
+        };
+
+        let resolver = Resolver::new(reqwest::Client::builder()).unwrap();
+        let pages: Vec<_> = collection
+            .pages(&resolver)
+            .map(|page| page.expect("embedded page needs no network fetch"))
+            .collect()
+            .await;
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].id, only_page.id);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::actor::CompoundActorReference;
@@ -14,6 +16,47 @@ pub struct Link {
 
     /// URL itself.
     pub href: url::Url,
+
+    /// MIME type of the linked resource, e.g. `application/x-mpegURL`
+    /// or `text/html`.
+    #[serde(rename = "mediaType", skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+
+    /// Width in pixels, for image/video links.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+
+    /// Height in pixels, for image/video links.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+
+    /// Width of one tile, for a tiled storyboard preview image such as
+    /// Peertube's.
+    #[serde(rename = "tileWidth", skip_serializing_if = "Option::is_none")]
+    pub tile_width: Option<u32>,
+
+    /// Height of one tile, for a tiled storyboard preview image.
+    #[serde(rename = "tileHeight", skip_serializing_if = "Option::is_none")]
+    pub tile_height: Option<u32>,
+
+    /// Duration of video covered by one tile, as an ISO-8601 duration
+    /// (e.g. `PT1S`).
+    #[serde(rename = "tileDuration", skip_serializing_if = "Option::is_none")]
+    pub tile_duration: Option<String>,
+}
+
+/// Tile geometry extracted from a Peertube-style storyboard preview,
+/// so a client can slice the sprite sheet into individual thumbnails.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Storyboard {
+    /// Width of one tile, in pixels.
+    pub tile_width: u32,
+
+    /// Height of one tile, in pixels.
+    pub tile_height: u32,
+
+    /// Duration of video covered by one tile, as an ISO-8601 duration.
+    pub tile_duration: String,
 }
 
 /// This enumeration keeps all types of links under one umbrella.
@@ -56,8 +99,32 @@ impl UrlReference {
             .into_iter()
             .next()
     }
+
+    /// Helper method to transform any enumeration option into a vector
+    /// of [Link]s. Variants that carry bare URLs rather than `Link`
+    /// objects (e.g. [UrlReference::Url]) have no media metadata to
+    /// offer, so they yield nothing.
+    pub fn as_links(&self) -> Vec<&Link> {
+        match self {
+            UrlReference::Link(link) => vec![link],
+            UrlReference::LinkList(links) => links.iter().collect(),
+            UrlReference::Url(_) | UrlReference::UrlList(_) | UrlReference::Invalid(_) => vec![],
+        }
+    }
 }
 
+/// Media type federated ActivityPub responses must be served with, and
+/// that requests for them should carry as `Accept`.
+pub const ACTIVITYSTREAMS_MIME: &str =
+    "application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"";
+
+/// Standard ActivityStreams `@context` IRI.
+const ACTIVITYSTREAMS_CONTEXT: &str = "https://www.w3.org/ns/activitystreams";
+
+/// Extension `@context` needed for `publicKey` and other security
+/// vocabulary terms, e.g. on `Person` actors.
+const SECURITY_CONTEXT: &str = "https://w3id.org/security/v1";
+
 /// Another foundation ActivityPub type - Object.
 /// Most of Fediverse data entities are represented as objects.
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -124,10 +191,37 @@ pub struct Object {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cc: Option<CompoundActorReference>,
 
-    /// Preview details.
+    /// Preview of this object, e.g. Peertube's tiled storyboard image.
+    /// See [Object::storyboard] for extracting tile geometry out of it.
+    #[cfg(feature = "more_properties")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<ObjectReference>,
+
+    /// Objects attached to this one, e.g. images attached to a note.
     #[cfg(feature = "more_properties")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    preview: Option<Entity>,
+    pub attachment: Option<Vec<ObjectReference>>,
+
+    /// When this object was published.
+    #[cfg(feature = "chrono")]
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "crate::temporal")]
+    pub published: Option<DateTime<Utc>>,
+
+    /// When this object was last edited.
+    #[cfg(feature = "chrono")]
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "crate::temporal")]
+    pub updated: Option<DateTime<Utc>>,
+
+    /// Start of the time window this object describes, e.g. for an
+    /// `Event`.
+    #[cfg(feature = "chrono")]
+    #[serde(rename = "startTime", default, skip_serializing_if = "Option::is_none", with = "crate::temporal")]
+    pub start_time: Option<DateTime<Utc>>,
+
+    /// End of the time window this object describes.
+    #[cfg(feature = "chrono")]
+    #[serde(rename = "endTime", default, skip_serializing_if = "Option::is_none", with = "crate::temporal")]
+    pub end_time: Option<DateTime<Utc>>,
 
     /// Object summary, short description.
     #[cfg(feature = "more_properties")]
@@ -139,13 +233,7 @@ impl Object {
     /// Creates basic object of `entity_type` with given `id`.
     /// Other properties are not set.
     pub fn new_with_entity_type(entity_type: EntityType, id: url::Url) -> Self {
-        Self {
-            entity: Entity::new(entity_type),
-            id,
-            name: None,
-            url: None,
-            to: None,
-        }
+        Self::new_with_entity(Entity::new(entity_type), id)
     }
 
     /// Creates basic object with given `entity` and `id`.
@@ -157,6 +245,22 @@ impl Object {
             name: None,
             url: None,
             to: None,
+            #[cfg(feature = "more_properties")]
+            cc: None,
+            #[cfg(feature = "more_properties")]
+            preview: None,
+            #[cfg(feature = "more_properties")]
+            attachment: None,
+            #[cfg(feature = "chrono")]
+            published: None,
+            #[cfg(feature = "chrono")]
+            updated: None,
+            #[cfg(feature = "chrono")]
+            start_time: None,
+            #[cfg(feature = "chrono")]
+            end_time: None,
+            #[cfg(feature = "more_properties")]
+            summary: None,
         }
     }
 
@@ -166,6 +270,45 @@ impl Object {
             .and_then(|x| x.any_url())
     }
 
+    /// Picks the `url` entry whose `media_type` matches a preference
+    /// from `accept` most closely, e.g. `["application/x-mpegURL",
+    /// "text/html"]` to prefer an HLS playlist over a plain web page.
+    /// Entries not in `accept` at all are never returned.
+    #[cfg(feature = "more_properties")]
+    pub fn best_media(&self, accept: &[&str]) -> Option<&Link> {
+        let links = self.url.as_ref()?.as_links();
+
+        accept.iter().find_map(|preferred| {
+            links.iter()
+                .find(|link| link.media_type.as_deref() == Some(*preferred))
+                .copied()
+        })
+    }
+
+    /// Extracts tile geometry from this object's `preview`, so a client
+    /// can slice a tiled storyboard sprite sheet (e.g. Peertube's) into
+    /// individual thumbnails. Returns `None` if there is no embedded
+    /// preview (a preview referenced only by URL is not fetched), if
+    /// none of its links carries both tile width and height, or if the
+    /// first link that does carries no tile duration.
+    #[cfg(feature = "more_properties")]
+    pub fn storyboard(&self) -> Option<Storyboard> {
+        let ObjectReference::Object(preview) = self.preview.as_ref()? else {
+            return None;
+        };
+
+        let link = preview.url.as_ref()?
+            .as_links()
+            .into_iter()
+            .find(|link| link.tile_width.is_some() && link.tile_height.is_some())?;
+
+        Some(Storyboard {
+            tile_width: link.tile_width?,
+            tile_height: link.tile_height?,
+            tile_duration: link.tile_duration.clone()?,
+        })
+    }
+
     /// Returns true if object addressee matches `pattern`.
     pub fn matches(&self, pattern: &str) -> bool {
         self.to.as_ref()
@@ -174,6 +317,35 @@ impl Object {
 
         // TODO: add cc match
     }
+
+    /// Attaches the standard ActivityStreams `@context`, plus the
+    /// `security/v1` extension context needed for `publicKey` and
+    /// similar terms, so this object serializes as a spec-compliant
+    /// top-level document.
+    pub fn with_default_context(mut self) -> Self {
+        self.entity.context = Some(Context::new(vec![
+            ACTIVITYSTREAMS_CONTEXT.to_string(),
+            SECURITY_CONTEXT.to_string(),
+        ]));
+
+        self
+    }
+
+    /// Serializes this object as JSON-LD, paired with the content-type
+    /// header value it must be served with, so callers don't have to
+    /// hand-assemble federated responses. Attaches the default
+    /// `@context` via [Object::with_default_context] first if one isn't
+    /// already set, so the result is spec-compliant JSON-LD regardless
+    /// of whether the caller remembered to call it.
+    pub fn to_federated_json(&self) -> Result<(String, &'static str), serde_json::Error> {
+        let body = if self.entity.context.is_some() {
+            serde_json::to_string(self)?
+        } else {
+            serde_json::to_string(&self.clone().with_default_context())?
+        };
+
+        Ok((body, ACTIVITYSTREAMS_MIME))
+    }
 }
 
 /// This trait exposes commonly used ActivityPub properties.
@@ -191,6 +363,24 @@ pub trait ObjectTrait {
 
     /// Returns type of this object.
     fn entity_type(&self) -> EntityType;
+
+    /// Returns when this object was published, if known.
+    #[cfg(feature = "chrono")]
+    fn published(&self) -> Option<DateTime<Utc>>;
+
+    /// Returns when this object was last edited, if known.
+    #[cfg(feature = "chrono")]
+    fn updated(&self) -> Option<DateTime<Utc>>;
+
+    /// Returns the start of the time window this object describes, if
+    /// any.
+    #[cfg(feature = "chrono")]
+    fn start_time(&self) -> Option<DateTime<Utc>>;
+
+    /// Returns the end of the time window this object describes, if
+    /// any.
+    #[cfg(feature = "chrono")]
+    fn end_time(&self) -> Option<DateTime<Utc>>;
 }
 
 impl ObjectTrait for Object {
@@ -205,6 +395,26 @@ impl ObjectTrait for Object {
     fn entity_type(&self) -> EntityType {
         self.entity.object_type
     }
+
+    #[cfg(feature = "chrono")]
+    fn published(&self) -> Option<DateTime<Utc>> {
+        self.published
+    }
+
+    #[cfg(feature = "chrono")]
+    fn updated(&self) -> Option<DateTime<Utc>> {
+        self.updated
+    }
+
+    #[cfg(feature = "chrono")]
+    fn start_time(&self) -> Option<DateTime<Utc>> {
+        self.start_time
+    }
+
+    #[cfg(feature = "chrono")]
+    fn end_time(&self) -> Option<DateTime<Utc>> {
+        self.end_time
+    }
 }
 
 /// Helper enumeration that wraps two ways to reference [Object].
@@ -228,3 +438,127 @@ impl ObjectReference {
         }
     }
 }
+
+#[cfg(all(test, feature = "more_properties"))]
+mod tests {
+    use super::*;
+
+    fn link(href: &str, media_type: &str) -> Link {
+        Link {
+            entity: Entity::new(EntityType::Link),
+            href: href.parse().unwrap(),
+            media_type: Some(media_type.to_string()),
+            width: None,
+            height: None,
+            tile_width: None,
+            tile_height: None,
+            tile_duration: None,
+        }
+    }
+
+    #[test]
+    fn best_media_prefers_earlier_entry_in_accept_list() {
+        let mut object = Object::new_with_entity_type(
+            EntityType::Video,
+            "https://example.com/videos/1".parse().unwrap(),
+        );
+
+        object.url = Some(UrlReference::LinkList(vec![
+            link("https://example.com/videos/1", "text/html"),
+            link("https://example.com/videos/1.m3u8", "application/x-mpegURL"),
+        ]));
+
+        let best = object
+            .best_media(&["application/x-mpegURL", "text/html"])
+            .expect("a matching link should be found");
+
+        assert_eq!(best.media_type.as_deref(), Some("application/x-mpegURL"));
+    }
+
+    #[test]
+    fn best_media_returns_none_when_nothing_matches() {
+        let mut object = Object::new_with_entity_type(
+            EntityType::Video,
+            "https://example.com/videos/1".parse().unwrap(),
+        );
+
+        object.url = Some(UrlReference::Link(link(
+            "https://example.com/videos/1",
+            "text/html",
+        )));
+
+        assert!(object.best_media(&["video/mp4"]).is_none());
+    }
+
+    #[test]
+    fn storyboard_extracts_tile_geometry_from_nested_preview() {
+        let mut tile_link = link("https://example.com/storyboards/1.jpg", "image/jpeg");
+        tile_link.tile_width = Some(192);
+        tile_link.tile_height = Some(108);
+        tile_link.tile_duration = Some("PT1S".to_string());
+
+        let mut preview = Object::new_with_entity_type(
+            EntityType::Image,
+            "https://example.com/storyboards/1".parse().unwrap(),
+        );
+        preview.url = Some(UrlReference::Link(tile_link));
+
+        let mut object = Object::new_with_entity_type(
+            EntityType::Video,
+            "https://example.com/videos/1".parse().unwrap(),
+        );
+        object.preview = Some(ObjectReference::Object(Box::new(preview)));
+
+        let storyboard = object.storyboard().expect("storyboard should be extracted");
+
+        assert_eq!(storyboard.tile_width, 192);
+        assert_eq!(storyboard.tile_height, 108);
+        assert_eq!(storyboard.tile_duration, "PT1S");
+    }
+
+    #[test]
+    fn storyboard_returns_none_without_tiled_preview() {
+        let object = Object::new_with_entity_type(
+            EntityType::Video,
+            "https://example.com/videos/1".parse().unwrap(),
+        );
+
+        assert!(object.storyboard().is_none());
+    }
+}
+
+#[cfg(test)]
+mod federated_json_tests {
+    use super::*;
+
+    #[test]
+    fn to_federated_json_attaches_default_context_when_missing() {
+        let object = Object::new_with_entity_type(
+            EntityType::Note,
+            "https://example.com/notes/1".parse().unwrap(),
+        );
+
+        let (body, content_type) = object.to_federated_json().unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(content_type, ACTIVITYSTREAMS_MIME);
+        assert!(parsed.get("@context").is_some());
+    }
+
+    #[test]
+    fn to_federated_json_keeps_an_already_set_context() {
+        let object = Object::new_with_entity_type(
+            EntityType::Note,
+            "https://example.com/notes/1".parse().unwrap(),
+        )
+        .with_default_context();
+
+        let (body, _) = object.to_federated_json().unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(
+            parsed["@context"],
+            serde_json::to_value(&object.entity.context).unwrap(),
+        );
+    }
+}
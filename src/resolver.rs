@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::object::{Link, Object, ObjectReference, UrlReference, ACTIVITYSTREAMS_MIME};
+
+/// Default number of HTTP redirects [Resolver] will follow before
+/// giving up.
+const DEFAULT_MAX_DEPTH: u32 = 8;
+
+/// Failure modes that can occur while turning an [ObjectReference] or
+/// [UrlReference] into a concrete [Object].
+#[derive(thiserror::Error, Debug)]
+pub enum ResolveError {
+    /// Underlying HTTP request failed outright.
+    #[error("transport error while fetching {url}: {source}")]
+    Transport {
+        url: url::Url,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// Server replied with a `Content-Type` that is not JSON of any
+    /// kind (e.g. `text/html`).
+    #[error("unexpected content type '{content_type}' received from {url}")]
+    UnexpectedContentType {
+        url: url::Url,
+        content_type: String,
+    },
+
+    /// Server replied with a redirect (3xx) but no usable `Location`
+    /// header, so there is nowhere to follow it to.
+    #[error("redirect response from {url} carried no usable Location header")]
+    BadRedirect { url: url::Url },
+
+    /// Body did not deserialize into [Object].
+    #[error("failed to deserialize object from {url}: {source}")]
+    Deserialize {
+        url: url::Url,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// Resolved object's `id` did not match the IRI that was requested.
+    /// This guards against a remote server spoofing another object's
+    /// identity by answering with a different `id`.
+    #[error("resolved object id '{actual}' does not match requested IRI '{expected}'")]
+    IdMismatch {
+        expected: url::Url,
+        actual: url::Url,
+    },
+
+    /// Too many indirections were followed without reaching an object.
+    #[error("exceeded maximum resolution depth of {0}")]
+    DepthExceeded(u32),
+
+    /// Reference carried no resolvable URL at all, e.g. a
+    /// [UrlReference::Invalid].
+    #[error("reference carries no resolvable URL")]
+    NoUrl,
+
+    /// A collection's `next` pointers were followed for more pages than
+    /// the fixed page limit, e.g. because a misbehaving or malicious
+    /// server loops `next` back to an earlier page.
+    #[error("exceeded maximum of {0} collection pages")]
+    TooManyPages(u32),
+}
+
+/// Rejects `object` unless its `id` matches `requested`, guarding
+/// against a remote server spoofing another object's identity by
+/// answering with a different `id` than the IRI that was fetched.
+fn check_id(requested: &url::Url, object: Object) -> Result<Object, ResolveError> {
+    if &object.id != requested {
+        return Err(ResolveError::IdMismatch {
+            expected: requested.clone(),
+            actual: object.id,
+        });
+    }
+
+    Ok(object)
+}
+
+/// Fetches and caches remote [Object]s by IRI.
+///
+/// Wraps an HTTP client and enforces the ActivityPub invariant that a
+/// fetched object's `id` must match the IRI it was fetched from, so
+/// [Resolvable] implementations never hand back a spoofed object.
+///
+/// [Resolver] follows `Location` redirects itself, up to
+/// [Resolver::with_max_depth], so the depth limit actually bounds
+/// something. A client that followed redirects on its own would bypass
+/// that limit entirely, so [Resolver::new] builds its own client with
+/// redirect-following disabled rather than trusting a caller-supplied
+/// one to have been configured that way.
+pub struct Resolver {
+    client: reqwest::Client,
+    max_depth: u32,
+    cache: Option<Mutex<HashMap<url::Url, Arc<Object>>>>,
+}
+
+impl Resolver {
+    /// Builds a resolver with the default depth limit and no caching.
+    /// `builder` is used as given, except its redirect policy is always
+    /// overridden to [reqwest::redirect::Policy::none], since [Resolver]
+    /// must see every 3xx itself to enforce [Resolver::with_max_depth].
+    pub fn new(builder: reqwest::ClientBuilder) -> Result<Self, reqwest::Error> {
+        let client = builder.redirect(reqwest::redirect::Policy::none()).build()?;
+
+        Ok(Self {
+            client,
+            max_depth: DEFAULT_MAX_DEPTH,
+            cache: None,
+        })
+    }
+
+    /// Sets the maximum number of indirections [Resolver::resolve_url]
+    /// will follow before returning [ResolveError::DepthExceeded].
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Enables an in-memory cache keyed by IRI, so repeated resolution
+    /// of the same reference only fetches it once.
+    pub fn with_cache(mut self) -> Self {
+        self.cache = Some(Mutex::new(HashMap::new()));
+        self
+    }
+
+    /// Fetches and deserializes the object at `url`, rejecting a reply
+    /// whose `id` does not match `url`.
+    pub async fn resolve_url(&self, url: &url::Url) -> Result<Arc<Object>, ResolveError> {
+        if let Some(cache) = &self.cache {
+            if let Some(object) = cache.lock().await.get(url) {
+                return Ok(Arc::clone(object));
+            }
+        }
+
+        let object: Object = self.fetch_with_depth(url, 0).await?;
+        let object = Arc::new(check_id(url, object)?);
+
+        if let Some(cache) = &self.cache {
+            cache.lock().await.insert(url.clone(), Arc::clone(&object));
+        }
+
+        Ok(object)
+    }
+
+    /// Fetches and deserializes whatever ActivityStreams document lives
+    /// at `url` as `T`, without the `Object`-specific id check or
+    /// caching. Used by types that page through collections, where
+    /// each page has its own shape but the transport concerns (accept
+    /// header, content type, depth limit) are identical.
+    pub(crate) async fn fetch<T>(&self, url: &url::Url) -> Result<T, ResolveError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.fetch_with_depth(url, 0).await
+    }
+
+    #[async_recursion::async_recursion]
+    async fn fetch_with_depth<T>(&self, url: &url::Url, depth: u32) -> Result<T, ResolveError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        if depth > self.max_depth {
+            return Err(ResolveError::DepthExceeded(self.max_depth));
+        }
+
+        let response = self
+            .client
+            .get(url.clone())
+            .header(reqwest::header::ACCEPT, ACTIVITYSTREAMS_MIME)
+            .send()
+            .await
+            .map_err(|source| ResolveError::Transport {
+                url: url.clone(),
+                source,
+            })?;
+
+        if response.status().is_redirection() {
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|location| url.join(location).ok())
+                .ok_or_else(|| ResolveError::BadRedirect { url: url.clone() })?;
+
+            return self.fetch_with_depth(&location, depth + 1).await;
+        }
+
+        let response = response
+            .error_for_status()
+            .map_err(|source| ResolveError::Transport {
+                url: url.clone(),
+                source,
+            })?;
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+
+        if !content_type.contains("json") {
+            return Err(ResolveError::UnexpectedContentType {
+                url: url.clone(),
+                content_type,
+            });
+        }
+
+        let body = response
+            .bytes()
+            .await
+            .map_err(|source| ResolveError::Transport {
+                url: url.clone(),
+                source,
+            })?;
+
+        serde_json::from_slice(&body).map_err(|source| ResolveError::Deserialize {
+            url: url.clone(),
+            source,
+        })
+    }
+}
+
+/// Implemented by anything that can be turned into a concrete [Object],
+/// either because it already embeds one or because it points at one by
+/// URL.
+#[async_trait::async_trait]
+pub trait Resolvable {
+    /// Resolves `self` into an [Object], fetching it through `resolver`
+    /// if it isn't already embedded.
+    async fn resolve(&self, resolver: &Resolver) -> Result<Arc<Object>, ResolveError>;
+}
+
+#[async_trait::async_trait]
+impl Resolvable for ObjectReference {
+    async fn resolve(&self, resolver: &Resolver) -> Result<Arc<Object>, ResolveError> {
+        match self {
+            ObjectReference::Object(object) => Ok(Arc::new((**object).clone())),
+            ObjectReference::Url(url) => resolver.resolve_url(url).await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Resolvable for Link {
+    async fn resolve(&self, resolver: &Resolver) -> Result<Arc<Object>, ResolveError> {
+        resolver.resolve_url(&self.href).await
+    }
+}
+
+#[async_trait::async_trait]
+impl Resolvable for UrlReference {
+    async fn resolve(&self, resolver: &Resolver) -> Result<Arc<Object>, ResolveError> {
+        let url = self.any_url().ok_or(ResolveError::NoUrl)?;
+
+        resolver.resolve_url(url).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::EntityType;
+
+    fn object_with_id(id: &str) -> Object {
+        Object::new_with_entity_type(EntityType::Note, id.parse().unwrap())
+    }
+
+    #[test]
+    fn check_id_accepts_matching_id() {
+        let requested: url::Url = "https://example.com/notes/1".parse().unwrap();
+        let object = object_with_id("https://example.com/notes/1");
+
+        assert!(check_id(&requested, object).is_ok());
+    }
+
+    #[test]
+    fn check_id_rejects_spoofed_id() {
+        let requested: url::Url = "https://example.com/notes/1".parse().unwrap();
+        let object = object_with_id("https://attacker.example/notes/1");
+
+        let error = check_id(&requested, object).unwrap_err();
+
+        assert!(matches!(error, ResolveError::IdMismatch { .. }));
+    }
+
+    /// Serves `responses` in order, one per accepted connection, on an
+    /// ephemeral localhost port. Lets tests drive [Resolver] against
+    /// crafted HTTP replies (redirects included) without a real network
+    /// or a mock-HTTP dependency.
+    async fn serve_responses(responses: Vec<String>) -> std::net::SocketAddr {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.shutdown().await.unwrap();
+            }
+        });
+
+        addr
+    }
+
+    fn http_response(status_line: &str, headers: &[(&str, String)], body: &str) -> String {
+        let mut response = format!("{status_line}\r\n");
+
+        for (name, value) in headers {
+            response.push_str(&format!("{name}: {value}\r\n"));
+        }
+
+        response.push_str(&format!("content-length: {}\r\n\r\n{}", body.len(), body));
+        response
+    }
+
+    #[tokio::test]
+    async fn fetch_with_depth_follows_redirect_then_succeeds() {
+        let start: url::Url = {
+            let addr = serve_responses(vec![
+                http_response("HTTP/1.1 302 Found", &[("location", "/final".to_string())], ""),
+                http_response(
+                    "HTTP/1.1 200 OK",
+                    &[("content-type", "application/activity+json".to_string())],
+                    r#"{"id":"http://placeholder/notes/1","type":"Note"}"#,
+                ),
+            ])
+            .await;
+
+            format!("http://{addr}/start").parse().unwrap()
+        };
+
+        let resolver = Resolver::new(reqwest::Client::builder()).unwrap();
+        let object: Object = resolver.fetch_with_depth(&start, 0).await.unwrap();
+
+        assert!(matches!(object.entity.object_type, EntityType::Note));
+    }
+
+    #[tokio::test]
+    async fn fetch_with_depth_errors_once_redirects_exceed_max_depth() {
+        let start: url::Url = {
+            let addr = serve_responses(vec![
+                http_response("HTTP/1.1 302 Found", &[("location", "/next".to_string())], ""),
+                http_response("HTTP/1.1 302 Found", &[("location", "/last".to_string())], ""),
+            ])
+            .await;
+
+            format!("http://{addr}/start").parse().unwrap()
+        };
+
+        let resolver = Resolver::new(reqwest::Client::builder())
+            .unwrap()
+            .with_max_depth(1);
+
+        let error = resolver
+            .fetch_with_depth::<Object>(&start, 0)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, ResolveError::DepthExceeded(1)));
+    }
+
+    #[tokio::test]
+    async fn fetch_with_depth_reports_bad_redirect_without_location() {
+        let start: url::Url = {
+            let addr = serve_responses(vec![http_response("HTTP/1.1 302 Found", &[], "")]).await;
+
+            format!("http://{addr}/start").parse().unwrap()
+        };
+
+        let resolver = Resolver::new(reqwest::Client::builder()).unwrap();
+
+        let error = resolver
+            .fetch_with_depth::<Object>(&start, 0)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, ResolveError::BadRedirect { .. }));
+    }
+}
@@ -0,0 +1,91 @@
+//! Shared RFC3339 (de)serialization for `Option<DateTime<Utc>>` fields.
+//!
+//! Fediverse servers are not consistent about emitting `Z` vs. an
+//! explicit offset, nor about sub-second precision, so deserialization
+//! accepts any valid RFC3339 variant rather than a fixed format string.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S>(value: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(timestamp) => serializer.serialize_str(&timestamp.to_rfc3339()),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|raw| {
+            DateTime::parse_from_rfc3339(&raw)
+                .map(|parsed| parsed.with_timezone(&Utc))
+                .map_err(serde::de::Error::custom)
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Wrapper {
+        #[serde(default, with = "super")]
+        timestamp: Option<DateTime<Utc>>,
+    }
+
+    fn parse(raw: &str) -> DateTime<Utc> {
+        let wrapper: Wrapper =
+            serde_json::from_value(serde_json::json!({ "timestamp": raw })).unwrap();
+
+        wrapper.timestamp.expect("timestamp should have parsed")
+    }
+
+    #[test]
+    fn accepts_zulu_suffix() {
+        assert_eq!(parse("2024-01-02T03:04:05Z").timestamp(), 1704164645);
+    }
+
+    #[test]
+    fn accepts_explicit_offset() {
+        assert_eq!(parse("2024-01-02T05:04:05+02:00").timestamp(), 1704164645);
+    }
+
+    #[test]
+    fn accepts_whole_second_precision() {
+        assert_eq!(parse("2024-01-02T03:04:05Z").timestamp_subsec_nanos(), 0);
+    }
+
+    #[test]
+    fn accepts_sub_second_precision() {
+        assert_eq!(
+            parse("2024-01-02T03:04:05.250Z").timestamp_subsec_millis(),
+            250,
+        );
+    }
+
+    #[test]
+    fn round_trips_through_serialize_and_deserialize() {
+        let original = Wrapper {
+            timestamp: Some(parse("2024-01-02T03:04:05.250Z")),
+        };
+
+        let json = serde_json::to_value(&original).unwrap();
+        let round_tripped: Wrapper = serde_json::from_value(json).unwrap();
+
+        assert_eq!(round_tripped.timestamp, original.timestamp);
+    }
+
+    #[test]
+    fn missing_timestamp_deserializes_to_none() {
+        let wrapper: Wrapper = serde_json::from_value(serde_json::json!({})).unwrap();
+
+        assert_eq!(wrapper.timestamp, None);
+    }
+}